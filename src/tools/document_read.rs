@@ -0,0 +1,1175 @@
+use super::pptx_read::extract_pptx_text;
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use base64::engine::general_purpose::STANDARD as BASE64_STANDARD;
+use base64::Engine as _;
+use serde_json::json;
+use std::io::Read;
+use std::sync::Arc;
+
+/// Maximum document file size (50 MB). Shared with [`super::documents_read`].
+pub(crate) const MAX_DOCUMENT_BYTES: u64 = 50 * 1024 * 1024;
+/// Default character limit returned to the LLM.
+pub(crate) const DEFAULT_MAX_CHARS: usize = 50_000;
+/// Hard ceiling regardless of what the caller requests.
+pub(crate) const MAX_OUTPUT_CHARS: usize = 200_000;
+/// Ceiling on how large a single embedded media part may be before its
+/// bytes are base64-inlined, regardless of what `inline_media_max_bytes`
+/// the caller requests. Base64 expands bytes by ~4/3 plus line-fold
+/// newlines, so this is sized so one inlined part's encoded payload still
+/// fits under `MAX_OUTPUT_CHARS` with room for the manifest header around
+/// it. [`render_media_manifest`] never slices a base64 block; a block that
+/// doesn't fit the char budget is dropped whole, so a larger cap would only
+/// produce blocks that can never be emitted.
+pub(crate) const MAX_INLINE_MEDIA_BYTES: u64 = 128 * 1024;
+/// Column width inlined base64 is wrapped at, mirroring the classic
+/// MIME/PEM 76-character line length so a transcript with inlined media
+/// doesn't end up with one arbitrarily long line.
+const BASE64_LINE_WIDTH: usize = 76;
+/// Bytes read for magic-byte MIME sniffing when a part's extension didn't
+/// resolve to anything — enough for every signature
+/// [`detect_mime_from_magic`] checks, without reading more of an
+/// unrecognized (and potentially untrusted) part than necessary.
+const MIME_SNIFF_BYTES: u64 = 64;
+
+/// Extract plain text from office documents and e-books in the workspace.
+///
+/// This generalizes [`super::pptx_read::PptxReadTool`] from PPTX-only
+/// extraction into a single tool covering every format this agent is
+/// likely to encounter in a document folder. All of these formats are ZIP
+/// archives containing XML (or, for EPUB, XHTML); the tool dispatches on
+/// file extension and keeps the ZIP-open logic shared while branching per
+/// container layout:
+///
+/// - `.pptx` — slide text via [`extract_pptx_text`]
+/// - `.docx` — `word/document.xml`, `<w:t>` runs broken by `<w:p>`
+/// - `.xlsx` — `xl/sharedStrings.xml` + `xl/worksheets/sheetN.xml`
+/// - `.odt` / `.ods` / `.odp` — OpenDocument `content.xml`
+/// - `.epub` — spine order from `META-INF/container.xml` → OPF, XHTML stripped
+pub struct DocumentReadTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl DocumentReadTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for DocumentReadTool {
+    fn name(&self) -> &str {
+        "document_read"
+    }
+
+    fn description(&self) -> &str {
+        "Extract plain text from an office document or e-book in the workspace. \
+         Supports .pptx, .docx, .xlsx, .odt, .ods, .odp and .epub. Returns all \
+         readable text, separated by slide/sheet/chapter markers. Useful for \
+         analyzing documents without manual copy-paste."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "path": {
+                    "type": "string",
+                    "description": "Path to the document file. Relative paths resolve from workspace; outside paths require policy allowlist."
+                },
+                "max_chars": {
+                    "type": "integer",
+                    "description": "Maximum characters to return (default: 50000, max: 200000)",
+                    "minimum": 1,
+                    "maximum": 200_000
+                },
+                "slide_range": {
+                    "type": "string",
+                    "description": "Restrict output to these slides/sheets/chapters, e.g. '3-7', '2,5,9' or open-ended '10-'. Applies to .pptx slides, .xlsx sheets and .epub chapters; ignored for .docx/.odt/.ods/.odp, which have no numbered sections. Applied before include/exclude."
+                },
+                "include": {
+                    "type": "string",
+                    "description": "Regex (plain substrings work too); only numbered blocks whose text matches are kept. For .docx/.odt/.ods/.odp the whole document is one block."
+                },
+                "exclude": {
+                    "type": "string",
+                    "description": "Regex (plain substrings work too); numbered blocks whose text matches are dropped. For .docx/.odt/.ods/.odp the whole document is one block."
+                },
+                "list_media": {
+                    "type": "boolean",
+                    "description": "Instead of extracting text, return a manifest of embedded media parts (images/audio/video) with name, detected MIME type and byte size (default: false)"
+                },
+                "inline_media_max_bytes": {
+                    "type": "integer",
+                    "description": "When list_media is set, base64-inline the bytes of any media part at or under this size, wrapped at 76 columns (default: not inlined; hard ceiling 131072 bytes, sized so one inlined part's base64 still fits under the output budget whole — no inlined block is ever truncated mid-payload, it's dropped entirely if the budget is full)",
+                    "minimum": 0
+                }
+            },
+            "required": ["path"]
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let path = args
+            .get("path")
+            .and_then(|v| v.as_str())
+            .ok_or_else(|| anyhow::anyhow!("Missing 'path' parameter"))?;
+
+        let max_chars = args
+            .get("max_chars")
+            .and_then(|v| v.as_u64())
+            .map(|n| {
+                usize::try_from(n)
+                    .unwrap_or(MAX_OUTPUT_CHARS)
+                    .min(MAX_OUTPUT_CHARS)
+            })
+            .unwrap_or(DEFAULT_MAX_CHARS);
+
+        let slide_range = match args.get("slide_range").and_then(|v| v.as_str()) {
+            Some(spec) => match parse_slide_range(spec) {
+                Ok(ranges) => Some(ranges),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid slide_range '{spec}': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let include = match args.get("include").and_then(|v| v.as_str()) {
+            Some(pattern) => match regex::Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid include pattern '{pattern}': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let exclude = match args.get("exclude").and_then(|v| v.as_str()) {
+            Some(pattern) => match regex::Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid exclude pattern '{pattern}': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let list_media = args
+            .get("list_media")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let inline_media_max_bytes = args.get("inline_media_max_bytes").and_then(|v| v.as_u64());
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        if !self.security.is_path_allowed(path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!("Path not allowed by security policy: {path}")),
+            });
+        }
+
+        if !self.security.record_action() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: action budget exhausted".into()),
+            });
+        }
+
+        let full_path = self.security.workspace_dir.join(path);
+
+        let resolved_path = match tokio::fs::canonicalize(&full_path).await {
+            Ok(p) => p,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to resolve file path: {e}")),
+                });
+            }
+        };
+
+        if !self.security.is_resolved_path_allowed(&resolved_path) {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(
+                    self.security
+                        .resolved_path_violation_message(&resolved_path),
+                ),
+            });
+        }
+
+        let Some(format) = DocumentFormat::from_path(&resolved_path) else {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some(format!(
+                    "Unsupported document type: {} (expected .pptx, .docx, .xlsx, .odt, .ods, .odp or .epub)",
+                    resolved_path.display()
+                )),
+            });
+        };
+
+        tracing::debug!("Reading document ({format:?}): {}", resolved_path.display());
+
+        match tokio::fs::metadata(&resolved_path).await {
+            Ok(meta) => {
+                if meta.len() > MAX_DOCUMENT_BYTES {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!(
+                            "Document too large: {} bytes (limit: {MAX_DOCUMENT_BYTES} bytes)",
+                            meta.len()
+                        )),
+                    });
+                }
+            }
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to read file metadata: {e}")),
+                });
+            }
+        }
+
+        let bytes = match tokio::fs::read(&resolved_path).await {
+            Ok(b) => b,
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Failed to read document file: {e}")),
+                });
+            }
+        };
+
+        let filters = DocumentFilterOptions {
+            slide_range,
+            include,
+            exclude,
+        };
+
+        // Document extraction is CPU-bound; run in blocking task
+        let text = match tokio::task::spawn_blocking(move || {
+            if list_media {
+                list_document_media(format, &bytes, inline_media_max_bytes)
+                    .map(|entries| render_media_manifest(&entries, max_chars))
+            } else {
+                extract_document_text(format, &bytes, &filters)
+            }
+        })
+        .await
+        {
+            Ok(Ok(t)) => t,
+            Ok(Err(e)) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Document extraction failed: {e}")),
+                });
+            }
+            Err(e) => {
+                return Ok(ToolResult {
+                    success: false,
+                    output: String::new(),
+                    error: Some(format!("Document extraction task panicked: {e}")),
+                });
+            }
+        };
+
+        if text.trim().is_empty() {
+            return Ok(ToolResult {
+                success: true,
+                output: "Document contains no extractable text (may be image-only)".into(),
+                error: None,
+            });
+        }
+
+        // list_media output is never char-sliced; render_media_manifest budgets its inlined blocks itself.
+        let output = if !list_media && text.chars().count() > max_chars {
+            let mut truncated: String = text.chars().take(max_chars).collect();
+            truncated.push_str("\n\n[... truncated, use max_chars to read more ...]");
+            truncated
+        } else {
+            text
+        };
+
+        Ok(ToolResult {
+            success: true,
+            output,
+            error: None,
+        })
+    }
+}
+
+/// One embedded media part discovered by [`list_media_in_archive`].
+pub(crate) struct MediaEntry {
+    pub(crate) name: String,
+    pub(crate) mime: String,
+    pub(crate) size: u64,
+    /// Base64-encoded bytes, present only when `size` was within the
+    /// caller's inline threshold.
+    pub(crate) data_base64: Option<String>,
+}
+
+/// ZIP path prefix media parts live under for this container format.
+/// `None` means there's no fixed prefix to anchor on (EPUB packages vary
+/// in layout); [`list_media_in_archive`] then falls back to extension
+/// sniffing across every entry.
+fn media_prefix(format: DocumentFormat) -> Option<&'static str> {
+    match format {
+        DocumentFormat::Pptx => Some("ppt/media/"),
+        DocumentFormat::Docx => Some("word/media/"),
+        DocumentFormat::Xlsx => Some("xl/media/"),
+        DocumentFormat::OdfText
+        | DocumentFormat::OdfSpreadsheet
+        | DocumentFormat::OdfPresentation => Some("Pictures/"),
+        DocumentFormat::Epub => None,
+    }
+}
+
+/// Open `bytes` as a ZIP and enumerate `format`'s embedded media parts.
+/// Shared entry point for [`DocumentReadTool`]; [`super::pptx_read::PptxReadTool`]
+/// calls [`list_media_in_archive`] directly since it already streams its
+/// archive from a file handle rather than an in-memory buffer.
+pub(crate) fn list_document_media(
+    format: DocumentFormat,
+    bytes: &[u8],
+    inline_max_bytes: Option<u64>,
+) -> anyhow::Result<Vec<MediaEntry>> {
+    let mut archive = zip::ZipArchive::new(std::io::Cursor::new(bytes))?;
+    list_media_in_archive(&mut archive, media_prefix(format), inline_max_bytes)
+}
+
+/// Walk every ZIP entry under `prefix` (or, when `prefix` is `None`, every
+/// entry whose extension looks like media) and report it as a
+/// [`MediaEntry`], inlining its base64-encoded bytes when `inline_max_bytes`
+/// permits and [`MAX_INLINE_MEDIA_BYTES`] doesn't override it down.
+pub(crate) fn list_media_in_archive<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    prefix: Option<&str>,
+    inline_max_bytes: Option<u64>,
+) -> anyhow::Result<Vec<MediaEntry>> {
+    let names: Vec<String> = archive
+        .file_names()
+        .filter(|name| match prefix {
+            Some(p) => name.starts_with(p),
+            None => looks_like_media(name),
+        })
+        .map(String::from)
+        .collect();
+
+    let inline_cap = inline_max_bytes.map(|n| n.min(MAX_INLINE_MEDIA_BYTES));
+
+    let mut entries = Vec::with_capacity(names.len());
+    for name in names {
+        let file = archive.by_name(&name)?;
+        let size = file.size();
+
+        let ext_mime = detect_mime_from_extension(&name);
+        let should_inline = inline_cap.is_some_and(|cap| size <= cap);
+
+        // Bound how much of the entry we actually pull off the
+        // *decompressing* stream, rather than trusting the ZIP central
+        // directory's declared `size`: a crafted entry can claim any
+        // uncompressed size it likes, so pre-allocating/reading up to that
+        // value would let a small archive trigger an unbounded read. Read
+        // at most a small sniff window when magic bytes are all we need,
+        // or at most the (already `MAX_INLINE_MEDIA_BYTES`-bounded) inline
+        // cap when we're inlining.
+        let read_limit = if should_inline {
+            inline_cap
+        } else if ext_mime.is_none() {
+            Some(MIME_SNIFF_BYTES)
+        } else {
+            None
+        };
+
+        let bytes = match read_limit {
+            Some(limit) => {
+                let mut buf = Vec::with_capacity(limit as usize);
+                file.take(limit).read_to_end(&mut buf)?;
+                Some(buf)
+            }
+            None => None,
+        };
+
+        let mime = ext_mime
+            .map(str::to_string)
+            .unwrap_or_else(|| detect_mime_from_magic(bytes.as_deref().unwrap_or(&[])).to_string());
+
+        let data_base64 = if should_inline {
+            bytes
+                .as_ref()
+                .map(|b| wrap_base64(&BASE64_STANDARD.encode(b), BASE64_LINE_WIDTH))
+        } else {
+            None
+        };
+
+        entries.push(MediaEntry { name, mime, size, data_base64 });
+    }
+
+    Ok(entries)
+}
+
+/// Whether `name`'s extension looks like a media asset, for formats (EPUB)
+/// with no fixed media directory to anchor on.
+fn looks_like_media(name: &str) -> bool {
+    detect_mime_from_extension(name).is_some()
+}
+
+/// MIME type from a part's file extension, for the common formats embedded
+/// documents carry.
+fn detect_mime_from_extension(name: &str) -> Option<&'static str> {
+    let ext = name.rsplit('.').next()?.to_ascii_lowercase();
+    Some(match ext.as_str() {
+        "png" => "image/png",
+        "jpg" | "jpeg" => "image/jpeg",
+        "gif" => "image/gif",
+        "bmp" => "image/bmp",
+        "svg" => "image/svg+xml",
+        "webp" => "image/webp",
+        "tiff" | "tif" => "image/tiff",
+        "emf" => "image/emf",
+        "wmf" => "image/wmf",
+        "mp3" => "audio/mpeg",
+        "wav" => "audio/wav",
+        "mp4" => "video/mp4",
+        "mov" => "video/quicktime",
+        "avi" => "video/x-msvideo",
+        "wmv" => "video/x-ms-wmv",
+        _ => return None,
+    })
+}
+
+/// MIME type from a part's leading magic bytes, for parts whose extension
+/// didn't resolve to anything in [`detect_mime_from_extension`].
+fn detect_mime_from_magic(bytes: &[u8]) -> &'static str {
+    if bytes.starts_with(b"\x89PNG\r\n\x1a\n") {
+        "image/png"
+    } else if bytes.starts_with(b"\xFF\xD8\xFF") {
+        "image/jpeg"
+    } else if bytes.starts_with(b"GIF87a") || bytes.starts_with(b"GIF89a") {
+        "image/gif"
+    } else if bytes.starts_with(b"BM") {
+        "image/bmp"
+    } else if bytes.len() >= 12 && &bytes[0..4] == b"RIFF" && &bytes[8..12] == b"WEBP" {
+        "image/webp"
+    } else if bytes.starts_with(b"%PDF") {
+        "application/pdf"
+    } else {
+        "application/octet-stream"
+    }
+}
+
+/// Insert a newline every `width` characters so a long base64 payload stays
+/// well-formed as a block of text rather than one unbroken line, mirroring
+/// the classic MIME/PEM line-folding convention.
+pub(crate) fn wrap_base64(encoded: &str, width: usize) -> String {
+    encoded
+        .as_bytes()
+        .chunks(width)
+        .map(|chunk| std::str::from_utf8(chunk).unwrap_or_default())
+        .collect::<Vec<_>>()
+        .join("\n")
+}
+
+/// Render a [`MediaEntry`] list as the `list_media` output: a manifest
+/// block listing every part's name/MIME/size, followed by one
+/// `--- <name> (<mime>, base64) ---` block per entry whose bytes were
+/// inlined.
+///
+/// Unlike the plain-text extraction paths, this output is never
+/// char-sliced against `max_chars`: slicing mid-base64 would hand the
+/// downstream decoder a corrupt payload. Instead, any inlined block that
+/// would take the output past `max_chars` is dropped whole and counted as
+/// omitted; later blocks that still fit are kept. The manifest lines
+/// themselves are always written in full.
+pub(crate) fn render_media_manifest(entries: &[MediaEntry], max_chars: usize) -> String {
+    if entries.is_empty() {
+        return "No embedded media found in this document.".to_string();
+    }
+
+    let mut result = String::from("--- Media ---\n");
+    for entry in entries {
+        if entry.data_base64.is_some() {
+            result.push_str(&format!(
+                "{} ({}, {} bytes, inlined below)\n",
+                entry.name, entry.mime, entry.size
+            ));
+        } else {
+            result.push_str(&format!("{} ({}, {} bytes)\n", entry.name, entry.mime, entry.size));
+        }
+    }
+    result.push('\n');
+
+    let mut omitted = 0usize;
+    for entry in entries {
+        if let Some(data) = &entry.data_base64 {
+            let block = format!("--- {} ({}, base64) ---\n{data}\n\n", entry.name, entry.mime);
+            if result.chars().count() + block.chars().count() > max_chars {
+                omitted += 1;
+                continue;
+            }
+            result.push_str(&block);
+        }
+    }
+
+    if omitted > 0 {
+        result.push_str(&format!(
+            "[... {omitted} inlined media block(s) omitted: output budget reached, raise max_chars or request fewer assets ...]\n"
+        ));
+    }
+
+    result
+}
+
+/// Container formats this tool knows how to open, keyed by file extension.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub(crate) enum DocumentFormat {
+    Pptx,
+    Docx,
+    Xlsx,
+    OdfText,
+    OdfSpreadsheet,
+    OdfPresentation,
+    Epub,
+}
+
+impl DocumentFormat {
+    pub(crate) fn from_path(path: &std::path::Path) -> Option<Self> {
+        let ext = path.extension()?.to_str()?.to_ascii_lowercase();
+        Some(match ext.as_str() {
+            "pptx" => Self::Pptx,
+            "docx" => Self::Docx,
+            "xlsx" => Self::Xlsx,
+            "odt" => Self::OdfText,
+            "ods" => Self::OdfSpreadsheet,
+            "odp" => Self::OdfPresentation,
+            "epub" => Self::Epub,
+            _ => return None,
+        })
+    }
+}
+
+/// Slide/paragraph filters shared by every non-PPTX-specific caller of
+/// [`extract_document_text`]; see [`super::pptx_read::PptxExtractOptions`]
+/// for why these live in a struct rather than as loose arguments.
+pub(crate) struct DocumentFilterOptions {
+    pub(crate) slide_range: Option<Vec<(u32, Option<u32>)>>,
+    pub(crate) include: Option<regex::Regex>,
+    pub(crate) exclude: Option<regex::Regex>,
+}
+
+/// Dispatch to the right extraction routine for `format`, applying
+/// `filters` where the format has numbered sections to filter.
+///
+/// PPTX gets the filters applied during streaming extraction via
+/// [`super::pptx_read::PptxExtractOptions`]. XLSX and EPUB already emit
+/// `--- Sheet N ---`/`--- Chapter N ---` blocks, so their filtering is a
+/// post-pass over the assembled text via [`filter_numbered_blocks`]. DOCX
+/// and ODF formats have no numbered sections to range over; `include`/
+/// `exclude` still narrow them at the whole-document level.
+pub(crate) fn extract_document_text(
+    format: DocumentFormat,
+    bytes: &[u8],
+    filters: &DocumentFilterOptions,
+) -> anyhow::Result<String> {
+    match format {
+        DocumentFormat::Pptx => {
+            let options = super::pptx_read::PptxExtractOptions {
+                include_notes: false,
+                max_chars: MAX_OUTPUT_CHARS,
+                slide_range: filters.slide_range.clone(),
+                include: filters.include.clone(),
+                exclude: filters.exclude.clone(),
+            };
+            extract_pptx_text(std::io::Cursor::new(bytes), &options)
+        }
+        DocumentFormat::Docx => extract_docx_text(bytes).map(|t| {
+            filter_numbered_blocks(&t, None, filters.include.as_ref(), filters.exclude.as_ref())
+        }),
+        DocumentFormat::Xlsx => extract_xlsx_text(bytes).map(|t| {
+            filter_numbered_blocks(
+                &t,
+                filters.slide_range.as_deref(),
+                filters.include.as_ref(),
+                filters.exclude.as_ref(),
+            )
+        }),
+        DocumentFormat::OdfText
+        | DocumentFormat::OdfSpreadsheet
+        | DocumentFormat::OdfPresentation => extract_odf_text(bytes).map(|t| {
+            filter_numbered_blocks(&t, None, filters.include.as_ref(), filters.exclude.as_ref())
+        }),
+        DocumentFormat::Epub => extract_epub_text(bytes).map(|t| {
+            filter_numbered_blocks(
+                &t,
+                filters.slide_range.as_deref(),
+                filters.include.as_ref(),
+                filters.exclude.as_ref(),
+            )
+        }),
+    }
+}
+
+/// Parse a `slide_range` spec like `"3-7"`, `"2,5,9"` or `"10-"` into a list
+/// of inclusive `(start, end)` ranges; `end = None` means open-ended.
+/// Shared by [`super::pptx_read`] and this module's own `slide_range`
+/// parameter.
+pub(crate) fn parse_slide_range(spec: &str) -> Result<Vec<(u32, Option<u32>)>, String> {
+    spec.split(',')
+        .map(str::trim)
+        .filter(|part| !part.is_empty())
+        .map(|part| {
+            if let Some((start, end)) = part.split_once('-') {
+                let start: u32 = start
+                    .trim()
+                    .parse()
+                    .map_err(|_| format!("invalid range start in '{part}'"))?;
+                let end = end.trim();
+                if end.is_empty() {
+                    Ok((start, None))
+                } else {
+                    let end: u32 = end
+                        .parse()
+                        .map_err(|_| format!("invalid range end in '{part}'"))?;
+                    Ok((start, Some(end)))
+                }
+            } else {
+                let n: u32 = part
+                    .parse()
+                    .map_err(|_| format!("invalid slide number '{part}'"))?;
+                Ok((n, Some(n)))
+            }
+        })
+        .collect()
+}
+
+/// Whether `num` falls inside any of the parsed `ranges`.
+pub(crate) fn slide_in_range(num: u32, ranges: &[(u32, Option<u32>)]) -> bool {
+    ranges
+        .iter()
+        .any(|(start, end)| num >= *start && end.is_none_or(|end| num <= end))
+}
+
+/// Whether `line` is one of this module's `--- Label N ---` block headers.
+fn is_block_header(line: &str) -> bool {
+    line.starts_with("--- ") && line.ends_with(" ---")
+}
+
+/// Post-filter an already-assembled document's `--- Label N ---` blocks by
+/// `ranges`/`include`/`exclude`. A block runs from one header line up to
+/// (not including) the next, so blank lines inside a block's own content
+/// (e.g. an EPUB chapter's paragraph breaks) can't be mistaken for a block
+/// boundary. Blocks whose header carries no number (or formats with no
+/// header at all, like plain DOCX/ODF text) pass the range filter
+/// unconditionally since there's nothing to number; `include`/`exclude`
+/// still apply to their content.
+fn filter_numbered_blocks(
+    text: &str,
+    ranges: Option<&[(u32, Option<u32>)]>,
+    include: Option<&regex::Regex>,
+    exclude: Option<&regex::Regex>,
+) -> String {
+    if ranges.is_none() && include.is_none() && exclude.is_none() {
+        return text.to_string();
+    }
+
+    let mut blocks: Vec<String> = Vec::new();
+    for line in text.lines() {
+        if blocks.is_empty() || is_block_header(line) {
+            blocks.push(String::new());
+        }
+        let current = blocks.last_mut().expect("just pushed if empty");
+        if !current.is_empty() {
+            current.push('\n');
+        }
+        current.push_str(line);
+    }
+
+    let mut result = String::new();
+    for block in &blocks {
+        if block.trim().is_empty() {
+            continue;
+        }
+
+        let header_num = block
+            .lines()
+            .next()
+            .filter(|line| is_block_header(line))
+            .and_then(|line| line.split_whitespace().find_map(|tok| tok.parse::<u32>().ok()));
+
+        if let (Some(ranges), Some(num)) = (ranges, header_num) {
+            if !slide_in_range(num, ranges) {
+                continue;
+            }
+        }
+
+        if let Some(re) = include {
+            if !re.is_match(block) {
+                continue;
+            }
+        }
+        if let Some(re) = exclude {
+            if re.is_match(block) {
+                continue;
+            }
+        }
+
+        result.push_str(block);
+        result.push_str("\n\n");
+    }
+    result
+}
+
+/// Extract text from DOCX bytes via `word/document.xml`.
+///
+/// Walks `<w:t>` runs, breaking on `<w:p>` paragraph boundaries.
+fn extract_docx_text(bytes: &[u8]) -> anyhow::Result<String> {
+    let cursor = std::io::Cursor::new(bytes);
+    let mut archive = zip::ZipArchive::new(cursor)?;
+
+    let mut file = archive.by_name("word/document.xml")?;
+    let mut xml_content = String::new();
+    file.read_to_string(&mut xml_content)?;
+
+    Ok(extract_tagged_paragraphs(&xml_content, "w:t", "w:p"))
+}
+
+/// Extract text from XLSX bytes: shared strings plus each worksheet's rows.
+fn extract_xlsx_text(bytes: &[u8]) -> anyhow::Result<String> {
+    let cursor = std::io::Cursor::new(bytes);
+    let mut archive = zip::ZipArchive::new(cursor)?;
+
+    let shared_strings = match archive.by_name("xl/sharedStrings.xml") {
+        Ok(mut file) => {
+            let mut xml_content = String::new();
+            file.read_to_string(&mut xml_content)?;
+            extract_elements(&xml_content, "si")
+                .into_iter()
+                .map(|si| decode_xml_entities(&strip_tags(&extract_elements(&si, "t").join(""))))
+                .collect::<Vec<_>>()
+        }
+        Err(_) => Vec::new(),
+    };
+
+    let mut sheet_files: Vec<String> = archive
+        .file_names()
+        .filter(|name| name.starts_with("xl/worksheets/sheet") && name.ends_with(".xml"))
+        .map(String::from)
+        .collect();
+
+    sheet_files.sort_by_key(|name| extract_numbered_part(name, "sheet").unwrap_or(0));
+
+    let mut result = String::new();
+    for (idx, sheet_name) in sheet_files.iter().enumerate() {
+        let mut file = archive.by_name(sheet_name)?;
+        let mut xml_content = String::new();
+        file.read_to_string(&mut xml_content)?;
+
+        let sheet_text = extract_sheet_rows(&xml_content, &shared_strings);
+        if !sheet_text.trim().is_empty() {
+            result.push_str(&format!("--- Sheet {} ---\n", idx + 1));
+            result.push_str(&sheet_text);
+            result.push_str("\n\n");
+        }
+    }
+
+    Ok(result)
+}
+
+/// Render one worksheet's `<row>`/`<c>` cells as tab-separated rows.
+fn extract_sheet_rows(xml: &str, shared_strings: &[String]) -> String {
+    let mut rows_out = Vec::new();
+
+    for row in extract_elements(xml, "row") {
+        let mut cells_out = Vec::new();
+        for (cell_tag, cell_body) in extract_elements_with_tags(&row, "c") {
+            let is_shared = cell_tag.contains("t=\"s\"");
+            let is_inline_str =
+                cell_tag.contains("t=\"str\"") || cell_tag.contains("t=\"inlineStr\"");
+            let value = extract_elements(&cell_body, "v").into_iter().next();
+
+            let text = if is_shared {
+                value
+                    .and_then(|v| v.trim().parse::<usize>().ok())
+                    .and_then(|idx| shared_strings.get(idx))
+                    .cloned()
+                    .unwrap_or_default()
+            } else if is_inline_str {
+                decode_xml_entities(&strip_tags(&extract_elements(&cell_body, "t").join("")))
+            } else {
+                value
+                    .map(|v| decode_xml_entities(v.trim()))
+                    .unwrap_or_default()
+            };
+
+            cells_out.push(text);
+        }
+        rows_out.push(cells_out.join("\t"));
+    }
+
+    rows_out.join("\n")
+}
+
+/// Extract text from an OpenDocument (`.odt`/`.ods`/`.odp`) `content.xml`.
+///
+/// Walks `<text:p>` paragraphs (which may wrap `<text:span>` runs).
+fn extract_odf_text(bytes: &[u8]) -> anyhow::Result<String> {
+    let cursor = std::io::Cursor::new(bytes);
+    let mut archive = zip::ZipArchive::new(cursor)?;
+
+    let mut file = archive.by_name("content.xml")?;
+    let mut xml_content = String::new();
+    file.read_to_string(&mut xml_content)?;
+
+    let mut result = String::new();
+    for para in extract_elements(&xml_content, "text:p") {
+        let text = decode_xml_entities(&strip_tags(&para));
+        if !text.trim().is_empty() {
+            result.push_str(text.trim());
+            result.push('\n');
+        }
+    }
+
+    Ok(result)
+}
+
+/// Extract text from an EPUB by walking the OPF spine in reading order.
+fn extract_epub_text(bytes: &[u8]) -> anyhow::Result<String> {
+    let cursor = std::io::Cursor::new(bytes);
+    let mut archive = zip::ZipArchive::new(cursor)?;
+
+    let container_xml = {
+        let mut file = archive.by_name("META-INF/container.xml")?;
+        let mut s = String::new();
+        file.read_to_string(&mut s)?;
+        s
+    };
+
+    let opf_path = extract_attr(&container_xml, "rootfile", "full-path")
+        .ok_or_else(|| anyhow::anyhow!("EPUB container.xml has no rootfile"))?;
+
+    let opf_xml = {
+        let mut file = archive.by_name(&opf_path)?;
+        let mut s = String::new();
+        file.read_to_string(&mut s)?;
+        s
+    };
+
+    let opf_dir = std::path::Path::new(&opf_path)
+        .parent()
+        .map(|p| p.to_string_lossy().to_string())
+        .unwrap_or_default();
+
+    // manifest: id -> href
+    let manifest: std::collections::HashMap<String, String> =
+        extract_elements_with_tags(&opf_xml, "item")
+            .into_iter()
+            .filter_map(|(tag, _)| {
+                let id = extract_attr_from_tag(&tag, "id")?;
+                let href = extract_attr_from_tag(&tag, "href")?;
+                Some((id, href))
+            })
+            .collect();
+
+    // spine: ordered list of idrefs
+    let spine: Vec<String> = extract_elements_with_tags(&opf_xml, "itemref")
+        .into_iter()
+        .filter_map(|(tag, _)| extract_attr_from_tag(&tag, "idref"))
+        .collect();
+
+    let mut result = String::new();
+    for (idx, idref) in spine.iter().enumerate() {
+        let Some(href) = manifest.get(idref) else {
+            continue;
+        };
+        let part_path = if opf_dir.is_empty() {
+            href.clone()
+        } else {
+            format!("{opf_dir}/{href}")
+        };
+
+        let Ok(mut file) = archive.by_name(&part_path) else {
+            continue;
+        };
+        let mut xhtml = String::new();
+        file.read_to_string(&mut xhtml)?;
+
+        let text = decode_xml_entities(strip_tags(&xhtml).trim());
+        if !text.is_empty() {
+            result.push_str(&format!("--- Chapter {} ---\n", idx + 1));
+            result.push_str(&text);
+            result.push_str("\n\n");
+        }
+    }
+
+    Ok(result)
+}
+
+/// Extract `text_tag` runs from `xml`, inserting a newline at each
+/// `break_tag` close (e.g. DOCX `<w:t>` runs broken by `<w:p>` paragraphs).
+fn extract_tagged_paragraphs(xml: &str, text_tag: &str, break_tag: &str) -> String {
+    let mut result = String::new();
+    let close_break = format!("</{break_tag}>");
+
+    let mut pos = 0;
+    while pos < xml.len() {
+        let rest = &xml[pos..];
+        let next_text = find_tag_open(rest, text_tag);
+        let next_break = rest.find(&close_break);
+
+        let text_comes_first = match (next_text, next_break) {
+            (Some(t), Some(b)) => t <= b,
+            (Some(_), None) => true,
+            (None, _) => false,
+        };
+
+        if text_comes_first {
+            let t = next_text.expect("text_comes_first implies next_text is Some");
+            let tag_start = pos + t;
+            let Some(tag_end_rel) = xml[tag_start..].find('>') else {
+                break;
+            };
+            let tag_end = tag_start + tag_end_rel;
+            let close = format!("</{text_tag}>");
+            let content_start = tag_end + 1;
+            let Some(close_rel) = xml[content_start..].find(&close) else {
+                break;
+            };
+            let content_end = content_start + close_rel;
+            result.push_str(&decode_xml_entities(&xml[content_start..content_end]));
+            pos = content_end + close.len();
+        } else if let Some(b) = next_break {
+            let break_pos = pos + b;
+            if !result.is_empty() && !result.ends_with('\n') {
+                result.push('\n');
+            }
+            pos = break_pos + close_break.len();
+        } else {
+            break;
+        }
+    }
+
+    result
+}
+
+/// Find the next occurrence of `<tag` in `xml` that is an actual opening of
+/// `tag` (next byte is space/`>`/`/`) rather than a bare prefix match of a
+/// longer tag name sharing it — e.g. `<w:t` inside `<w:tbl>`/`<w:tr>`/
+/// `<w:tc>`/`<w:tab/>`. Same guard as [`extract_elements_with_tags`], pulled
+/// out standalone since [`extract_tagged_paragraphs`] only needs the start
+/// position, not the whole element.
+fn find_tag_open(xml: &str, tag: &str) -> Option<usize> {
+    let prefix = format!("<{tag}");
+    let mut search_from = 0;
+    loop {
+        let start = search_from + xml[search_from..].find(&prefix)?;
+        let after = xml.as_bytes().get(start + prefix.len()).copied();
+        if matches!(after, Some(b' ') | Some(b'>') | Some(b'/')) {
+            return Some(start);
+        }
+        search_from = start + prefix.len();
+    }
+}
+
+/// Find the inner content of every `<tag ...>...</tag>` element in `xml`;
+/// a self-closing `<tag .../>` yields an empty string. Naive (no nesting awareness beyond "first close wins"),
+/// matching this module's "simple extraction that handles most cases"
+/// approach rather than pulling in a full XML parser.
+pub(crate) fn extract_elements(xml: &str, tag: &str) -> Vec<String> {
+    extract_elements_with_tags(xml, tag)
+        .into_iter()
+        .map(|(_, content)| content)
+        .collect()
+}
+
+/// Like [`extract_elements`] but also returns the opening tag (including
+/// attributes) alongside each element's inner content.
+pub(crate) fn extract_elements_with_tags(xml: &str, tag: &str) -> Vec<(String, String)> {
+    let open_prefix = format!("<{tag}");
+    let close_tag = format!("</{tag}>");
+    let mut results = Vec::new();
+    let mut pos = 0;
+
+    while let Some(start_rel) = xml[pos..].find(&open_prefix) {
+        let start = pos + start_rel;
+        // Guard against matching a longer tag name sharing this prefix, e.g. "c" vs "color".
+        let after_name = xml.as_bytes().get(start + open_prefix.len()).copied();
+        if !matches!(after_name, Some(b' ') | Some(b'>') | Some(b'/')) {
+            pos = start + open_prefix.len();
+            continue;
+        }
+
+        let Some(tag_end_rel) = xml[start..].find('>') else {
+            break;
+        };
+        let tag_end = start + tag_end_rel;
+        let opening_tag = xml[start..=tag_end].to_string();
+
+        if xml.as_bytes()[tag_end - 1] == b'/' {
+            results.push((opening_tag, String::new()));
+            pos = tag_end + 1;
+            continue;
+        }
+
+        let content_start = tag_end + 1;
+        let Some(close_rel) = xml[content_start..].find(&close_tag) else {
+            break;
+        };
+        let content_end = content_start + close_rel;
+        results.push((opening_tag, xml[content_start..content_end].to_string()));
+        pos = content_end + close_tag.len();
+    }
+
+    results
+}
+
+/// Strip all `<...>` markup, leaving the concatenated text content.
+fn strip_tags(xml: &str) -> String {
+    let mut out = String::new();
+    let mut in_tag = false;
+    for c in xml.chars() {
+        match c {
+            '<' => in_tag = true,
+            '>' => in_tag = false,
+            _ if !in_tag => out.push(c),
+            _ => {}
+        }
+    }
+    out
+}
+
+/// Read an attribute's value off the first `<tag ...>` occurrence in `xml`.
+fn extract_attr(xml: &str, tag: &str, attr: &str) -> Option<String> {
+    let start = find_tag_open(xml, tag)?;
+    let end = xml[start..].find('>')? + start;
+    extract_attr_from_tag(&xml[start..=end], attr)
+}
+
+/// Read an attribute's value out of an already-isolated opening tag string.
+pub(crate) fn extract_attr_from_tag(tag: &str, attr: &str) -> Option<String> {
+    let needle = format!("{attr}=\"");
+    let start = tag.find(&needle)? + needle.len();
+    let end = tag[start..].find('"')? + start;
+    Some(tag[start..end].to_string())
+}
+
+/// Extract a numeric suffix from filenames like "xl/worksheets/sheet3.xml",
+/// mirroring [`super::pptx_read::extract_slide_number`]'s sort-by-number
+/// approach for sheets. EPUB chapters follow the OPF spine order instead.
+fn extract_numbered_part(name: &str, prefix: &str) -> Option<u32> {
+    let filename = name.rsplit('/').next()?;
+    let num_str = filename.strip_prefix(prefix)?.strip_suffix(".xml")?;
+    num_str.parse().ok()
+}
+
+/// Decode the handful of XML entities this subsystem's extractors emit.
+/// Shared by [`super::pptx_read::extract_text_from_xml`] so decoding stays
+/// in one place as more formats are added.
+pub(crate) fn decode_xml_entities(text: &str) -> String {
+    text.replace("&amp;", "&")
+        .replace("&lt;", "<")
+        .replace("&gt;", ">")
+        .replace("&quot;", "\"")
+        .replace("&apos;", "'")
+        .replace("&#39;", "'")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_slide_range_single_and_list() {
+        assert_eq!(parse_slide_range("3").unwrap(), vec![(3, Some(3))]);
+        assert_eq!(
+            parse_slide_range("2,5,9").unwrap(),
+            vec![(2, Some(2)), (5, Some(5)), (9, Some(9))]
+        );
+    }
+
+    #[test]
+    fn parse_slide_range_bounded_and_open_ended() {
+        assert_eq!(parse_slide_range("3-7").unwrap(), vec![(3, Some(7))]);
+        assert_eq!(parse_slide_range("10-").unwrap(), vec![(10, None)]);
+    }
+
+    #[test]
+    fn parse_slide_range_rejects_garbage() {
+        assert!(parse_slide_range("abc").is_err());
+        assert!(parse_slide_range("3-x").is_err());
+    }
+
+    #[test]
+    fn slide_in_range_checks_bounds_and_open_end() {
+        let ranges = vec![(2, Some(4)), (10, None)];
+        assert!(slide_in_range(2, &ranges));
+        assert!(slide_in_range(4, &ranges));
+        assert!(!slide_in_range(5, &ranges));
+        assert!(slide_in_range(100, &ranges));
+    }
+
+    #[test]
+    fn wrap_base64_folds_at_width() {
+        assert_eq!(wrap_base64("abcdefgh", 4), "abcd\nefgh");
+        assert_eq!(wrap_base64("abc", 4), "abc");
+        assert_eq!(wrap_base64("", 4), "");
+    }
+
+    #[test]
+    fn find_tag_open_skips_prefixed_tags() {
+        let xml = "<w:tbl><w:tr><w:tc><w:t>hi</w:t></w:tc></w:tr></w:tbl><w:t>end</w:t>";
+        let start = find_tag_open(xml, "w:t").expect("should find real <w:t>");
+        assert!(xml[start..].starts_with("<w:t>hi"));
+    }
+
+    #[test]
+    fn find_tag_open_matches_self_closing_and_none() {
+        assert_eq!(find_tag_open("<w:t/>", "w:t"), Some(0));
+        assert_eq!(find_tag_open("<w:tab/>", "w:t"), None);
+        assert_eq!(find_tag_open("no tags here", "w:t"), None);
+    }
+
+    #[test]
+    fn filter_numbered_blocks_keeps_internal_blank_lines_in_one_block() {
+        // A blank line inside Chapter 1's own body must not be mistaken for
+        // a block boundary: both "para1" and "para2" belong to Chapter 1,
+        // which is out of range here and must be dropped in full.
+        let text = "--- Chapter 1 ---\npara1\n\npara2\n\n--- Chapter 2 ---\npara3\n\n";
+        let ranges = vec![(2, Some(2))];
+        let filtered = filter_numbered_blocks(text, Some(&ranges), None, None);
+        assert!(!filtered.contains("Chapter 1"));
+        assert!(!filtered.contains("para1"));
+        assert!(!filtered.contains("para2"));
+        assert!(filtered.contains("Chapter 2"));
+        assert!(filtered.contains("para3"));
+    }
+}
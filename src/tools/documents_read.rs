@@ -0,0 +1,359 @@
+use super::document_read::{
+    extract_document_text, DocumentFilterOptions, DocumentFormat, DEFAULT_MAX_CHARS,
+    MAX_DOCUMENT_BYTES, MAX_OUTPUT_CHARS,
+};
+use super::traits::{Tool, ToolResult};
+use crate::security::SecurityPolicy;
+use async_trait::async_trait;
+use serde_json::json;
+use std::sync::Arc;
+
+/// Number of files extracted concurrently. Extraction is CPU-bound and each
+/// file already runs in its own `spawn_blocking` task, so this just bounds
+/// how many blocking tasks (and open archives) exist at once.
+const MAX_CONCURRENT_EXTRACTIONS: usize = 4;
+
+/// Extract text from every document matching a set of paths or a workspace
+/// glob, returning a map of path → extracted text.
+///
+/// Built on the same per-format extraction as
+/// [`super::document_read::DocumentReadTool`], but fans out across files
+/// through a bounded [`tokio::sync::Semaphore`] rather than extracting one
+/// file at a time, so a folder of a couple hundred decks doesn't spawn a
+/// couple hundred blocking tasks simultaneously.
+pub struct DocumentsReadTool {
+    security: Arc<SecurityPolicy>,
+}
+
+impl DocumentsReadTool {
+    pub fn new(security: Arc<SecurityPolicy>) -> Self {
+        Self { security }
+    }
+}
+
+#[async_trait]
+impl Tool for DocumentsReadTool {
+    fn name(&self) -> &str {
+        "documents_read"
+    }
+
+    fn description(&self) -> &str {
+        "Extract plain text from multiple office documents/e-books in the workspace at once. \
+         Accepts an explicit list of paths and/or a workspace-relative glob pattern (e.g. \
+         'reports/*.pptx'). Returns a JSON object mapping each path to its extracted text. \
+         Useful for summarizing a whole folder of presentations or documents in one call."
+    }
+
+    fn parameters_schema(&self) -> serde_json::Value {
+        json!({
+            "type": "object",
+            "properties": {
+                "paths": {
+                    "type": "array",
+                    "items": { "type": "string" },
+                    "description": "Explicit list of document paths. Relative paths resolve from workspace; outside paths require policy allowlist."
+                },
+                "glob": {
+                    "type": "string",
+                    "description": "Workspace-relative glob, e.g. 'reports/*.pptx' or 'reports/q*-*.pptx'. Any number of '*' wildcards in the filename is supported; non-recursive (only matches files directly inside the given directory)."
+                },
+                "max_chars": {
+                    "type": "integer",
+                    "description": "Maximum characters to return per file (default: 50000, max: 200000). An aggregate cap of 200000 characters also applies across all files.",
+                    "minimum": 1,
+                    "maximum": 200_000
+                }
+            }
+        })
+    }
+
+    async fn execute(&self, args: serde_json::Value) -> anyhow::Result<ToolResult> {
+        let max_chars = args
+            .get("max_chars")
+            .and_then(|v| v.as_u64())
+            .map(|n| {
+                usize::try_from(n)
+                    .unwrap_or(MAX_OUTPUT_CHARS)
+                    .min(MAX_OUTPUT_CHARS)
+            })
+            .unwrap_or(DEFAULT_MAX_CHARS);
+
+        if self.security.is_rate_limited() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("Rate limit exceeded: too many actions in the last hour".into()),
+            });
+        }
+
+        let mut paths: Vec<String> = args
+            .get("paths")
+            .and_then(|v| v.as_array())
+            .map(|items| {
+                items
+                    .iter()
+                    .filter_map(|v| v.as_str().map(String::from))
+                    .collect()
+            })
+            .unwrap_or_default();
+
+        if let Some(pattern) = args.get("glob").and_then(|v| v.as_str()) {
+            let workspace_dir = self.security.workspace_dir.clone();
+            let pattern = pattern.to_string();
+            let pattern_for_error = pattern.clone();
+            let expanded =
+                tokio::task::spawn_blocking(move || expand_glob(&workspace_dir, &pattern)).await;
+            match expanded {
+                Ok(Ok(matches)) => paths.extend(matches),
+                Ok(Err(e)) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Failed to expand glob '{pattern_for_error}': {e}")),
+                    });
+                }
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Glob expansion task panicked: {e}")),
+                    });
+                }
+            }
+        }
+
+        paths.sort();
+        paths.dedup();
+
+        if paths.is_empty() {
+            return Ok(ToolResult {
+                success: false,
+                output: String::new(),
+                error: Some("No files matched: provide 'paths' and/or 'glob'".into()),
+            });
+        }
+
+        let semaphore = Arc::new(tokio::sync::Semaphore::new(MAX_CONCURRENT_EXTRACTIONS));
+        let mut handles = Vec::with_capacity(paths.len());
+
+        for path in &paths {
+            let security = self.security.clone();
+            let semaphore = semaphore.clone();
+            let path = path.clone();
+            handles.push(tokio::spawn(async move {
+                let _permit = semaphore
+                    .acquire_owned()
+                    .await
+                    .expect("semaphore is never closed");
+                extract_one_document(&security, &path, max_chars).await
+            }));
+        }
+
+        let mut remaining_budget = MAX_OUTPUT_CHARS;
+        let mut results = serde_json::Map::with_capacity(paths.len());
+
+        for (path, handle) in paths.into_iter().zip(handles) {
+            let outcome = match handle.await {
+                Ok(r) => r,
+                Err(e) => Err(format!("extraction task panicked: {e}")),
+            };
+
+            let entry = match outcome {
+                Err(e) => format!("Error: {e}"),
+                Ok(text) if remaining_budget == 0 => {
+                    let _ = text;
+                    "[skipped: aggregate output cap reached, see max_chars]".to_string()
+                }
+                Ok(text) => {
+                    let char_count = text.chars().count();
+                    if char_count > remaining_budget {
+                        let truncated: String = text.chars().take(remaining_budget).collect();
+                        remaining_budget = 0;
+                        format!("{truncated}\n\n[... truncated: aggregate output cap reached ...]")
+                    } else {
+                        remaining_budget -= char_count;
+                        text
+                    }
+                }
+            };
+
+            results.insert(path, json!(entry));
+        }
+
+        Ok(ToolResult {
+            success: true,
+            output: serde_json::to_string_pretty(&results)?,
+            error: None,
+        })
+    }
+}
+
+/// Validate and extract a single document, mirroring the per-file checks in
+/// [`super::document_read::DocumentReadTool::execute`] (path allowlist,
+/// canonicalization, resolved-path allowlist, size cap, one `record_action`
+/// budget unit) but returning a plain error string instead of a [`ToolResult`].
+async fn extract_one_document(
+    security: &SecurityPolicy,
+    path: &str,
+    max_chars: usize,
+) -> Result<String, String> {
+    if !security.is_path_allowed(path) {
+        return Err(format!("path not allowed by security policy: {path}"));
+    }
+
+    if !security.record_action() {
+        return Err("rate limit exceeded: action budget exhausted".into());
+    }
+
+    let full_path = security.workspace_dir.join(path);
+
+    let resolved_path = tokio::fs::canonicalize(&full_path)
+        .await
+        .map_err(|e| format!("failed to resolve file path: {e}"))?;
+
+    if !security.is_resolved_path_allowed(&resolved_path) {
+        return Err(security.resolved_path_violation_message(&resolved_path));
+    }
+
+    let Some(format) = DocumentFormat::from_path(&resolved_path) else {
+        return Err(format!(
+            "unsupported document type: {}",
+            resolved_path.display()
+        ));
+    };
+
+    let meta = tokio::fs::metadata(&resolved_path)
+        .await
+        .map_err(|e| format!("failed to read file metadata: {e}"))?;
+    if meta.len() > MAX_DOCUMENT_BYTES {
+        return Err(format!(
+            "document too large: {} bytes (limit: {MAX_DOCUMENT_BYTES} bytes)",
+            meta.len()
+        ));
+    }
+
+    let bytes = tokio::fs::read(&resolved_path)
+        .await
+        .map_err(|e| format!("failed to read document file: {e}"))?;
+
+    // No per-file slide_range/include/exclude here: this tool fans out
+    // across a whole folder, so filtering stays a per-document_read concern.
+    let filters = DocumentFilterOptions {
+        slide_range: None,
+        include: None,
+        exclude: None,
+    };
+    let text = tokio::task::spawn_blocking(move || extract_document_text(format, &bytes, &filters))
+        .await
+        .map_err(|e| format!("extraction task panicked: {e}"))?
+        .map_err(|e| format!("extraction failed: {e}"))?;
+
+    if text.trim().is_empty() {
+        return Ok("[no extractable text, may be image-only]".to_string());
+    }
+
+    if text.chars().count() > max_chars {
+        let mut truncated: String = text.chars().take(max_chars).collect();
+        truncated.push_str("\n\n[... truncated, use max_chars to read more ...]");
+        Ok(truncated)
+    } else {
+        Ok(text)
+    }
+}
+
+/// Expand a workspace-relative glob of the form `dir/pattern.ext`, where
+/// `pattern` may contain any number of `*` wildcards (see
+/// [`matches_wildcard`]). Non-recursive: only matches files directly inside
+/// `dir`. Runs on a blocking thread via [`tokio::task::spawn_blocking`]
+/// since `std::fs::read_dir` would otherwise block the async runtime.
+fn expand_glob(workspace_dir: &std::path::Path, pattern: &str) -> std::io::Result<Vec<String>> {
+    let (dir_part, file_pattern) = match pattern.rsplit_once('/') {
+        Some((dir, file)) => (dir, file),
+        None => ("", pattern),
+    };
+
+    let scan_dir = if dir_part.is_empty() {
+        workspace_dir.to_path_buf()
+    } else {
+        workspace_dir.join(dir_part)
+    };
+
+    let mut matches = Vec::new();
+    for entry in std::fs::read_dir(&scan_dir)? {
+        let entry = entry?;
+        if !entry.file_type()?.is_file() {
+            continue;
+        }
+        let name = entry.file_name();
+        let Some(name) = name.to_str() else {
+            continue;
+        };
+        if matches_wildcard(name, file_pattern) {
+            matches.push(if dir_part.is_empty() {
+                name.to_string()
+            } else {
+                format!("{dir_part}/{name}")
+            });
+        }
+    }
+
+    matches.sort();
+    Ok(matches)
+}
+
+/// Match `name` against `pattern`, where `pattern` may contain any number of
+/// `*` wildcards (each matching zero or more characters).
+fn matches_wildcard(name: &str, pattern: &str) -> bool {
+    let segments: Vec<&str> = pattern.split('*').collect();
+    if segments.len() == 1 {
+        return name == pattern;
+    }
+
+    let mut rest = name;
+
+    if let Some(first) = segments.first() {
+        if !rest.starts_with(first) {
+            return false;
+        }
+        rest = &rest[first.len()..];
+    }
+
+    for segment in &segments[1..segments.len() - 1] {
+        if segment.is_empty() {
+            continue;
+        }
+        match rest.find(segment) {
+            Some(idx) => rest = &rest[idx + segment.len()..],
+            None => return false,
+        }
+    }
+
+    match segments.last() {
+        Some(last) => rest.ends_with(last),
+        None => true,
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn matches_wildcard_no_wildcard_requires_exact_match() {
+        assert!(matches_wildcard("report.pptx", "report.pptx"));
+        assert!(!matches_wildcard("report.pptx", "other.pptx"));
+    }
+
+    #[test]
+    fn matches_wildcard_single_star() {
+        assert!(matches_wildcard("report.pptx", "*.pptx"));
+        assert!(matches_wildcard("report.pptx", "report.*"));
+        assert!(!matches_wildcard("report.docx", "*.pptx"));
+    }
+
+    #[test]
+    fn matches_wildcard_multiple_stars() {
+        assert!(matches_wildcard("q1-report-final.pptx", "q1-*-final.*"));
+        assert!(!matches_wildcard("q1-report-draft.pptx", "q1-*-final.*"));
+    }
+}
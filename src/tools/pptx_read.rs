@@ -2,7 +2,7 @@ use super::traits::{Tool, ToolResult};
 use crate::security::SecurityPolicy;
 use async_trait::async_trait;
 use serde_json::json;
-use std::io::{Cursor, Read};
+use std::io::Read;
 use std::sync::Arc;
 
 /// Maximum PPTX file size (50 MB).
@@ -16,6 +16,11 @@ const MAX_OUTPUT_CHARS: usize = 200_000;
 ///
 /// PPTX files are ZIP archives containing XML. This tool extracts text
 /// from all slides by parsing the `ppt/slides/slide*.xml` files.
+///
+/// For callers that want a single tool covering PPTX alongside DOCX, XLSX
+/// and other office/e-book formats, see
+/// [`super::document_read::DocumentReadTool`], which dispatches to the
+/// extraction routines in this module for `.pptx` inputs.
 pub struct PptxReadTool {
     security: Arc<SecurityPolicy>,
 }
@@ -51,6 +56,31 @@ impl Tool for PptxReadTool {
                     "description": "Maximum characters to return (default: 50000, max: 200000)",
                     "minimum": 1,
                     "maximum": 200_000
+                },
+                "include_notes": {
+                    "type": "boolean",
+                    "description": "Also extract each slide's speaker notes, appended as a '--- Slide N Notes ---' block (default: false)"
+                },
+                "slide_range": {
+                    "type": "string",
+                    "description": "Restrict output to these slides, e.g. '3-7', '2,5,9' or open-ended '10-'. Applied before include/exclude."
+                },
+                "include": {
+                    "type": "string",
+                    "description": "Regex (plain substrings work too); only slides whose text matches are kept."
+                },
+                "exclude": {
+                    "type": "string",
+                    "description": "Regex (plain substrings work too); slides whose text matches are dropped."
+                },
+                "list_media": {
+                    "type": "boolean",
+                    "description": "Instead of extracting slide text, return a manifest of embedded media under ppt/media/ (images, etc.) with name, detected MIME type and byte size (default: false)"
+                },
+                "inline_media_max_bytes": {
+                    "type": "integer",
+                    "description": "When list_media is set, base64-inline the bytes of any media part at or under this size, wrapped at 76 columns (default: not inlined; hard ceiling 131072 bytes, sized so one inlined part's base64 still fits under the output budget whole — no inlined block is ever truncated mid-payload, it's dropped entirely if the budget is full)",
+                    "minimum": 0
                 }
             },
             "required": ["path"]
@@ -73,6 +103,60 @@ impl Tool for PptxReadTool {
             })
             .unwrap_or(DEFAULT_MAX_CHARS);
 
+        let include_notes = args
+            .get("include_notes")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let slide_range = match args.get("slide_range").and_then(|v| v.as_str()) {
+            Some(spec) => match super::document_read::parse_slide_range(spec) {
+                Ok(ranges) => Some(ranges),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid slide_range '{spec}': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let include = match args.get("include").and_then(|v| v.as_str()) {
+            Some(pattern) => match regex::Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid include pattern '{pattern}': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let exclude = match args.get("exclude").and_then(|v| v.as_str()) {
+            Some(pattern) => match regex::Regex::new(pattern) {
+                Ok(re) => Some(re),
+                Err(e) => {
+                    return Ok(ToolResult {
+                        success: false,
+                        output: String::new(),
+                        error: Some(format!("Invalid exclude pattern '{pattern}': {e}")),
+                    });
+                }
+            },
+            None => None,
+        };
+
+        let list_media = args
+            .get("list_media")
+            .and_then(|v| v.as_bool())
+            .unwrap_or(false);
+
+        let inline_media_max_bytes = args.get("inline_media_max_bytes").and_then(|v| v.as_u64());
+
         if self.security.is_rate_limited() {
             return Ok(ToolResult {
                 success: false,
@@ -145,19 +229,35 @@ impl Tool for PptxReadTool {
             }
         }
 
-        let bytes = match tokio::fs::read(&resolved_path).await {
-            Ok(b) => b,
-            Err(e) => {
-                return Ok(ToolResult {
-                    success: false,
-                    output: String::new(),
-                    error: Some(format!("Failed to read PPTX file: {e}")),
-                });
-            }
+        let path_for_blocking = resolved_path.clone();
+        let options = PptxExtractOptions {
+            include_notes,
+            max_chars,
+            slide_range,
+            include,
+            exclude,
         };
 
-        // PPTX extraction is CPU-bound; run in blocking task
-        let text = match tokio::task::spawn_blocking(move || extract_pptx_text(&bytes)).await {
+        // PPTX extraction is CPU-bound; run in blocking task. Stream the
+        // archive from a file handle rather than loading the whole (up to
+        // MAX_PPTX_BYTES) file into memory up front: extract_pptx_text stops
+        // decompressing further slides once it has enough text for max_chars.
+        let text = match tokio::task::spawn_blocking(move || {
+            let file = std::fs::File::open(&path_for_blocking)?;
+            if list_media {
+                let mut archive = zip::ZipArchive::new(std::io::BufReader::new(file))?;
+                let entries = super::document_read::list_media_in_archive(
+                    &mut archive,
+                    Some("ppt/media/"),
+                    inline_media_max_bytes,
+                )?;
+                Ok(super::document_read::render_media_manifest(&entries, max_chars))
+            } else {
+                extract_pptx_text(std::io::BufReader::new(file), &options)
+            }
+        })
+        .await
+        {
             Ok(Ok(t)) => t,
             Ok(Err(e)) => {
                 return Ok(ToolResult {
@@ -183,7 +283,8 @@ impl Tool for PptxReadTool {
             });
         }
 
-        let output = if text.chars().count() > max_chars {
+        // list_media output is never char-sliced; render_media_manifest budgets its inlined blocks itself.
+        let output = if !list_media && text.chars().count() > max_chars {
             let mut truncated: String = text.chars().take(max_chars).collect();
             truncated.push_str("\n\n[... truncated, use max_chars to read more ...]");
             truncated
@@ -199,17 +300,39 @@ impl Tool for PptxReadTool {
     }
 }
 
-/// Extract text from PPTX bytes by parsing slide XML files.
-fn extract_pptx_text(bytes: &[u8]) -> anyhow::Result<String> {
-    let cursor = Cursor::new(bytes);
-    let mut archive = zip::ZipArchive::new(cursor)?;
+/// Options controlling [`extract_pptx_text`]. Grouped into a struct because
+/// the parameter list kept growing (notes, the output budget, then slide
+/// selection) and threading five loose arguments through a `spawn_blocking`
+/// closure stopped being readable.
+pub(crate) struct PptxExtractOptions {
+    pub(crate) include_notes: bool,
+    pub(crate) max_chars: usize,
+    /// Inclusive `(start, end)` slide-number ranges; `end = None` means
+    /// open-ended. `None` means "every slide".
+    pub(crate) slide_range: Option<Vec<(u32, Option<u32>)>>,
+    /// Only slides whose extracted text matches are kept.
+    pub(crate) include: Option<regex::Regex>,
+    /// Slides whose extracted text matches are dropped.
+    pub(crate) exclude: Option<regex::Regex>,
+}
+
+/// Extract text from a PPTX archive by parsing slide XML files, stopping
+/// once the accumulated text reaches `max_chars` so a tiny budget on a huge
+/// deck doesn't pay to decompress and parse every remaining slide.
+///
+/// Shared with [`super::document_read::DocumentReadTool`], which dispatches
+/// to this function for `.pptx` inputs rather than duplicating the ZIP/XML
+/// handling.
+pub(crate) fn extract_pptx_text<R: Read + std::io::Seek>(
+    reader: R,
+    options: &PptxExtractOptions,
+) -> anyhow::Result<String> {
+    let mut archive = zip::ZipArchive::new(reader)?;
 
     // Collect slide files and sort them numerically
     let mut slide_files: Vec<String> = archive
         .file_names()
-        .filter(|name| {
-            name.starts_with("ppt/slides/slide") && name.ends_with(".xml")
-        })
+        .filter(|name| name.starts_with("ppt/slides/slide") && name.ends_with(".xml"))
         .map(String::from)
         .collect();
 
@@ -220,37 +343,185 @@ fn extract_pptx_text(bytes: &[u8]) -> anyhow::Result<String> {
         num_a.cmp(&num_b)
     });
 
+    // Drop out-of-range slides right after sorting, before any XML is read.
+    if let Some(ranges) = &options.slide_range {
+        slide_files.retain(|name| {
+            let num = extract_slide_number(name).unwrap_or(0);
+            super::document_read::slide_in_range(num, ranges)
+        });
+    }
+
     let mut result = String::new();
 
     for (idx, slide_name) in slide_files.iter().enumerate() {
+        let slide_num = extract_slide_number(slide_name).unwrap_or(idx as u32 + 1);
+
         let mut file = archive.by_name(slide_name)?;
         let mut xml_content = String::new();
         file.read_to_string(&mut xml_content)?;
+        drop(file);
 
         let slide_text = extract_text_from_xml(&xml_content);
 
+        if let Some(re) = &options.include {
+            if !re.is_match(&slide_text) {
+                continue;
+            }
+        }
+        if let Some(re) = &options.exclude {
+            if re.is_match(&slide_text) {
+                continue;
+            }
+        }
+
         if !slide_text.trim().is_empty() {
-            result.push_str(&format!("--- Slide {} ---\n", idx + 1));
+            result.push_str(&format!("--- Slide {slide_num} ---\n"));
             result.push_str(&slide_text);
             result.push_str("\n\n");
         }
+
+        if options.include_notes {
+            if let Some(notes_text) = extract_slide_notes(&mut archive, slide_name)? {
+                if !notes_text.trim().is_empty() {
+                    result.push_str(&format!("--- Slide {slide_num} Notes ---\n"));
+                    result.push_str(&notes_text);
+                    result.push_str("\n\n");
+                }
+            }
+        }
+
+        if result.chars().count() >= options.max_chars {
+            let mut truncated: String = result.chars().take(options.max_chars).collect();
+            truncated.push_str("\n\n[... truncated, use max_chars to read more ...]");
+            return Ok(truncated);
+        }
     }
 
     Ok(result)
 }
 
+/// Resolve and extract the speaker-notes text for one slide, if it has any.
+///
+/// The slide → notes mapping lives in the slide's own relationships part,
+/// `ppt/slides/_rels/slideN.xml.rels`, as a `Relationship` whose `Type`
+/// ends in `notesSlide` and whose `Target` is a path relative to
+/// `ppt/slides/`.
+fn extract_slide_notes<R: Read + std::io::Seek>(
+    archive: &mut zip::ZipArchive<R>,
+    slide_name: &str,
+) -> anyhow::Result<Option<String>> {
+    let filename = match slide_name.rsplit('/').next() {
+        Some(f) => f,
+        None => return Ok(None),
+    };
+    let rels_name = format!("ppt/slides/_rels/{filename}.rels");
+
+    let mut rels_file = match archive.by_name(&rels_name) {
+        Ok(f) => f,
+        Err(_) => return Ok(None),
+    };
+    let mut rels_xml = String::new();
+    rels_file.read_to_string(&mut rels_xml)?;
+    drop(rels_file);
+
+    let notes_target = super::document_read::extract_elements_with_tags(&rels_xml, "Relationship")
+        .into_iter()
+        .find_map(|(tag, _)| {
+            let rel_type = super::document_read::extract_attr_from_tag(&tag, "Type")?;
+            if rel_type.ends_with("notesSlide") {
+                super::document_read::extract_attr_from_tag(&tag, "Target")
+            } else {
+                None
+            }
+        });
+
+    let Some(target) = notes_target else {
+        return Ok(None);
+    };
+
+    let notes_path = resolve_zip_relative_path("ppt/slides", &target);
+
+    let mut notes_file = match archive.by_name(&notes_path) {
+        Ok(f) => f,
+        Err(_) => return Ok(None),
+    };
+    let mut notes_xml = String::new();
+    notes_file.read_to_string(&mut notes_xml)?;
+
+    Ok(Some(extract_text_from_xml(&notes_xml)))
+}
+
+/// Resolve a `Target` path from a `.rels` file (which may start with `../`)
+/// relative to `base_dir`, the directory the relationship part itself lives
+/// alongside (e.g. `ppt/slides` for `ppt/slides/_rels/slide1.xml.rels`).
+fn resolve_zip_relative_path(base_dir: &str, target: &str) -> String {
+    let mut parts: Vec<&str> = base_dir.split('/').filter(|s| !s.is_empty()).collect();
+    for segment in target.split('/') {
+        match segment {
+            "" | "." => {}
+            ".." => {
+                parts.pop();
+            }
+            other => parts.push(other),
+        }
+    }
+    parts.join("/")
+}
+
 /// Extract slide number from filename like "ppt/slides/slide1.xml"
-fn extract_slide_number(name: &str) -> Option<u32> {
+pub(crate) fn extract_slide_number(name: &str) -> Option<u32> {
     let filename = name.rsplit('/').next()?;
-    let num_str = filename
-        .strip_prefix("slide")?
-        .strip_suffix(".xml")?;
+    let num_str = filename.strip_prefix("slide")?.strip_suffix(".xml")?;
     num_str.parse().ok()
 }
 
 /// Extract text content from OOXML by finding <a:t> elements.
 /// This is a simple regex-based extraction that handles most cases.
+///
+/// Tables (`<a:tbl>`) are handled separately from the surrounding paragraph
+/// text: each `<a:tbl>...</a:tbl>` span is pulled out and rendered as a
+/// tab-separated grid via [`extract_table_grid`] so tabular slides don't
+/// collapse into an unreadable run-on sentence, and the non-table text
+/// around it still goes through the original paragraph-based extraction.
 fn extract_text_from_xml(xml: &str) -> String {
+    let mut result = String::new();
+    let mut pos = 0;
+
+    while pos < xml.len() {
+        match xml[pos..].find("<a:tbl") {
+            Some(tbl_start_rel) => {
+                let tbl_start = pos + tbl_start_rel;
+                result.push_str(&extract_paragraph_text(&xml[pos..tbl_start]));
+
+                match xml[tbl_start..].find("</a:tbl>") {
+                    Some(tbl_end_rel) => {
+                        let tbl_end = tbl_start + tbl_end_rel + "</a:tbl>".len();
+                        result.push_str(&extract_table_grid(&xml[tbl_start..tbl_end]));
+                        if !result.ends_with('\n') {
+                            result.push('\n');
+                        }
+                        pos = tbl_end;
+                    }
+                    None => {
+                        // Unterminated table tag; treat the rest as plain text.
+                        result.push_str(&extract_paragraph_text(&xml[tbl_start..]));
+                        break;
+                    }
+                }
+            }
+            None => {
+                result.push_str(&extract_paragraph_text(&xml[pos..]));
+                break;
+            }
+        }
+    }
+
+    result
+}
+
+/// Extract `<a:t>` run text from non-tabular OOXML, breaking paragraphs
+/// (`</a:p>`) and line breaks (`<a:br/>`) onto new lines.
+fn extract_paragraph_text(xml: &str) -> String {
     let mut text = String::new();
     let mut in_text_element = false;
     let mut current_text = String::new();
@@ -289,11 +560,69 @@ fn extract_text_from_xml(xml: &str) -> String {
         }
     }
 
-    // Decode common XML entities
-    text.replace("&amp;", "&")
-        .replace("&lt;", "<")
-        .replace("&gt;", ">")
-        .replace("&quot;", "\"")
-        .replace("&apos;", "'")
-        .replace("&#39;", "'")
+    super::document_read::decode_xml_entities(&text)
+}
+
+/// Render a `<a:tbl>...</a:tbl>` span as tab-separated cells and
+/// newline-separated rows: `<a:tr>` rows of `<a:tc>` cells, each cell's
+/// `<a:t>` runs joined into a single line.
+fn extract_table_grid(tbl_xml: &str) -> String {
+    let rows = super::document_read::extract_elements_with_tags(tbl_xml, "a:tr");
+
+    let lines: Vec<String> = rows
+        .into_iter()
+        .map(|(_, row_xml)| {
+            let cells = super::document_read::extract_elements_with_tags(&row_xml, "a:tc");
+            cells
+                .into_iter()
+                .map(|(_, cell_xml)| {
+                    super::document_read::extract_elements(&cell_xml, "a:t")
+                        .into_iter()
+                        .map(|t| super::document_read::decode_xml_entities(&t))
+                        .collect::<Vec<_>>()
+                        .join(" ")
+                        .trim()
+                        .to_string()
+                })
+                .collect::<Vec<_>>()
+                .join("\t")
+        })
+        .collect();
+
+    lines.join("\n")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn resolve_zip_relative_path_handles_parent_refs() {
+        assert_eq!(
+            resolve_zip_relative_path("ppt/slides", "../notesSlides/notesSlide1.xml"),
+            "ppt/notesSlides/notesSlide1.xml"
+        );
+    }
+
+    #[test]
+    fn resolve_zip_relative_path_same_dir() {
+        assert_eq!(
+            resolve_zip_relative_path("ppt/slides", "slide1.xml"),
+            "ppt/slides/slide1.xml"
+        );
+    }
+
+    #[test]
+    fn resolve_zip_relative_path_ignores_dot_segments() {
+        assert_eq!(
+            resolve_zip_relative_path("ppt/slides", "./slide1.xml"),
+            "ppt/slides/slide1.xml"
+        );
+    }
+
+    #[test]
+    fn extract_slide_number_parses_and_rejects() {
+        assert_eq!(extract_slide_number("ppt/slides/slide12.xml"), Some(12));
+        assert_eq!(extract_slide_number("ppt/slides/notesSlide1.xml"), None);
+    }
 }